@@ -20,40 +20,58 @@
 //! [`OsString`](std::ffi::OsString)):
 //!
 //! ```rust
-//! fn main() {
-//!     println!(
-//!         "User's Name            whoami::realname():    {}",
-//!         whoami::realname(),
-//!     );
-//!     println!(
-//!         "User's Username        whoami::username():    {}",
-//!         whoami::username(),
-//!     );
-//!     println!(
-//!         "User's Language        whoami::lang():        {:?}",
-//!         whoami::lang().collect::<Vec<String>>(),
-//!     );
-//!     println!(
-//!         "Device's Pretty Name   whoami::devicename():  {}",
-//!         whoami::devicename(),
-//!     );
-//!     println!(
-//!         "Device's Hostname      whoami::hostname():    {}",
-//!         whoami::hostname(),
-//!     );
-//!     println!(
-//!         "Device's Platform      whoami::platform():    {}",
-//!         whoami::platform(),
-//!     );
-//!     println!(
-//!         "Device's OS Distro     whoami::distro():      {}",
-//!         whoami::distro(),
-//!     );
-//!     println!(
-//!         "Device's Desktop Env.  whoami::desktop_env(): {}",
-//!         whoami::desktop_env(),
-//!     );
-//! }
+//! println!(
+//!     "User's Name            whoami::realname():    {}",
+//!     whoami::realname(),
+//! );
+//! println!(
+//!     "User's Username        whoami::username():    {}",
+//!     whoami::username(),
+//! );
+//! println!(
+//!     "User's Language        whoami::lang():        {:?}",
+//!     whoami::lang().collect::<Vec<String>>(),
+//! );
+//! println!(
+//!     "Device's Pretty Name   whoami::devicename():  {}",
+//!     whoami::devicename(),
+//! );
+//! println!(
+//!     "Device's Hostname      whoami::hostname():    {}",
+//!     whoami::hostname(),
+//! );
+//! println!(
+//!     "Device's Platform      whoami::platform():    {}",
+//!     whoami::platform(),
+//! );
+//! println!(
+//!     "Device's OS Distro     whoami::distro():      {}",
+//!     whoami::distro(),
+//! );
+//! println!(
+//!     "Device's Desktop Env.  whoami::desktop_env(): {}",
+//!     whoami::desktop_env(),
+//! );
+//! println!(
+//!     "Device's CPU Arch      whoami::arch():        {}",
+//!     whoami::arch(),
+//! );
+//! println!(
+//!     "Device's Bitness       whoami::bitness():     {}",
+//!     whoami::bitness(),
+//! );
+//! println!(
+//!     "Device's OS Info       whoami::os_info():     {}",
+//!     whoami::os_info(),
+//! );
+//! println!(
+//!     "Device's Desktop Envs. whoami::desktop_envs(): {:?}",
+//!     whoami::desktop_envs().collect::<Vec<whoami::DesktopEnv>>(),
+//! );
+//! println!(
+//!     "Device's Packaging     whoami::packaging():   {}",
+//!     whoami::packaging(),
+//! );
 //! ```
 
 #![warn(missing_docs)]
@@ -103,6 +121,12 @@ pub enum DesktopEnv {
     Ermine,
     /// Default desktop environment for Redox
     Orbital,
+    /// Desktop environment that aims to be simple and resource friendly
+    Enlightenment,
+    /// Lightweight Qt-based desktop environment
+    Lxqt,
+    /// Desktop environment built for Ubuntu (superseded by Gnome)
+    Unity,
     /// Unknown desktop environment
     Unknown(String),
 }
@@ -134,6 +158,9 @@ impl std::fmt::Display for DesktopEnv {
                 DesktopEnv::Ubuntu => "Ubuntu",
                 DesktopEnv::Ermine => "Ermine",
                 DesktopEnv::Orbital => "Orbital",
+                DesktopEnv::Enlightenment => "Enlightenment",
+                DesktopEnv::Lxqt => "LXQT",
+                DesktopEnv::Unity => "Unity",
                 DesktopEnv::Unknown(a) => a,
             }
         )
@@ -160,6 +187,31 @@ pub enum Platform {
     Unknown(String),
 }
 
+impl Platform {
+    /// Get the broad OS family this platform belongs to.
+    ///
+    /// Since [`Platform`](crate::Platform) is
+    /// [`non_exhaustive`](https://doc.rust-lang.org/reference/attributes/type_system.html#the-non_exhaustive-attribute),
+    /// new variants may be added for future platforms; `family()` lets
+    /// callers bucket current and future platforms into a small, stable set
+    /// without matching on every variant themselves.
+    pub fn family(&self) -> Family {
+        match self {
+            Platform::Windows | Platform::Xbox => Family::Windows,
+            Platform::Linux
+            | Platform::Bsd
+            | Platform::MacOS
+            | Platform::Ios
+            | Platform::Android
+            | Platform::Fuchsia
+            | Platform::Redox => Family::Unix,
+            Platform::Nintendo | Platform::PlayStation | Platform::Unknown(_) => {
+                Family::Other
+            }
+        }
+    }
+}
+
 impl std::fmt::Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         if let Platform::Unknown(_) = self {
@@ -187,6 +239,256 @@ impl std::fmt::Display for Platform {
     }
 }
 
+/// Broad operating-system family, as returned by
+/// [`Platform::family()`](crate::Platform::family).
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum Family {
+    /// Linux, BSD, `MacOS`, iOS, Android, Fuchsia, Redox, and other
+    /// UNIX-like operating systems
+    Unix,
+    /// Any version of Windows, including Xbox
+    Windows,
+    /// Reserved for a `wasm32` target with no detectable host OS. Currently
+    /// unreachable: [`platform()`](crate::platform) resolves `wasm32` builds
+    /// to the real host OS parsed from the browser's user agent, so it is
+    /// never returned today, but it's kept for parity with non-browser wasm
+    /// runtimes that may not expose one.
+    Wasm,
+    /// A platform family that doesn't fit the above buckets (e.g. game
+    /// consoles)
+    Other,
+}
+
+impl std::fmt::Display for Family {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Family::Unix => "Unix",
+                Family::Windows => "Windows",
+                Family::Wasm => "Wasm",
+                Family::Other => "Other",
+            }
+        )
+    }
+}
+
+/// Which CPU Architecture
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum Arch {
+    X86,
+    X64,
+    Arm,
+    Arm64,
+    Mips,
+    Mips64,
+    Powerpc,
+    Powerpc64,
+    Riscv64,
+    S390x,
+    Sparc64,
+    Wasm,
+    Unknown(String),
+}
+
+impl Arch {
+    /// Convert an [`Arch`] into the string used for the `target_arch` cfg
+    /// attribute (<https://doc.rust-lang.org/reference/conditional-compilation.html#target_arch>).
+    pub fn as_str(&self) -> &str {
+        match self {
+            Arch::X86 => "x86",
+            Arch::X64 => "x86_64",
+            Arch::Arm => "arm",
+            Arch::Arm64 => "aarch64",
+            Arch::Mips => "mips",
+            Arch::Mips64 => "mips64",
+            Arch::Powerpc => "powerpc",
+            Arch::Powerpc64 => "powerpc64",
+            Arch::Riscv64 => "riscv64",
+            Arch::S390x => "s390x",
+            Arch::Sparc64 => "sparc64",
+            Arch::Wasm => "wasm32",
+            Arch::Unknown(a) => a,
+        }
+    }
+
+    // Parse the value of `std::env::consts::ARCH` (or platform-specific
+    // equivalents like `uname -m`) into an [`Arch`].
+    //
+    // Unused on `wasm32`, where `arch()` returns `Arch::Wasm` directly
+    // without going through the native (unix/windows) modules that call
+    // this.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    fn from_str(arch: &str) -> Self {
+        match arch {
+            "x86" | "i386" | "i486" | "i586" | "i686" => Arch::X86,
+            "x86_64" | "amd64" => Arch::X64,
+            "arm" | "armv7" | "armv7l" | "armv6l" => Arch::Arm,
+            "aarch64" | "arm64" => Arch::Arm64,
+            "mips" => Arch::Mips,
+            "mips64" => Arch::Mips64,
+            "powerpc" | "ppc" => Arch::Powerpc,
+            "powerpc64" | "ppc64" | "ppc64le" => Arch::Powerpc64,
+            "riscv64" => Arch::Riscv64,
+            "s390x" => Arch::S390x,
+            "sparc64" => Arch::Sparc64,
+            "wasm32" => Arch::Wasm,
+            a => Arch::Unknown(a.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Arch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Arch::Unknown(_) = self {
+            write!(f, "Unknown: ")?;
+        }
+
+        write!(
+            f,
+            "{}",
+            match self {
+                Arch::X86 => "x86",
+                Arch::X64 => "x86_64",
+                Arch::Arm => "ARM",
+                Arch::Arm64 => "ARM64",
+                Arch::Mips => "MIPS",
+                Arch::Mips64 => "MIPS64",
+                Arch::Powerpc => "PowerPC",
+                Arch::Powerpc64 => "PowerPC64",
+                Arch::Riscv64 => "RISC-V64",
+                Arch::S390x => "S390x",
+                Arch::Sparc64 => "Sparc64",
+                Arch::Wasm => "WASM",
+                Arch::Unknown(a) => a,
+            }
+        )
+    }
+}
+
+/// Which kind of pointer width
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum Width {
+    /// 32-bit
+    X32,
+    /// 64-bit
+    X64,
+    /// Unknown bitness
+    Unknown,
+}
+
+impl std::fmt::Display for Width {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Width::X32 => "32-bit",
+                Width::X64 => "64-bit",
+                Width::Unknown => "Unknown",
+            }
+        )
+    }
+}
+
+/// Structured information about the operating system's distribution and
+/// version, as opposed to the single flattened [`String`](std::string::String)
+/// returned by [`distro()`](crate::distro).
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct OsInfo {
+    /// Name of the distribution or operating system (e.g. "Fedora", "Windows")
+    pub name: Option<String>,
+    /// Major version number
+    pub major: Option<String>,
+    /// Minor version number
+    pub minor: Option<String>,
+    /// Patch version number
+    pub patch: Option<String>,
+    /// Release codename (e.g. "bullseye")
+    pub codename: Option<String>,
+    /// Edition or variant (e.g. "Workstation Edition")
+    pub edition: Option<String>,
+}
+
+impl OsInfo {
+    // Join the version components with '.', stopping at the first missing
+    // one (no version at all without a major).
+    fn version(&self) -> Option<String> {
+        let mut version = self.major.clone()?;
+
+        if let Some(minor) = &self.minor {
+            version.push('.');
+            version.push_str(minor);
+
+            if let Some(patch) = &self.patch {
+                version.push('.');
+                version.push_str(patch);
+            }
+        }
+
+        Some(version)
+    }
+}
+
+impl std::fmt::Display for OsInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = self.name.as_deref().unwrap_or("Unknown");
+        write!(f, "{}", name)?;
+
+        if let Some(version) = self.version() {
+            write!(f, " {}", version)?;
+        }
+
+        if let Some(edition) = &self.edition {
+            write!(f, " ({})", edition)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Sandboxed/containerized application packaging format the process is
+/// running under, if any.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum Packaging {
+    /// Running inside a Flatpak sandbox
+    Flatpak,
+    /// Running inside a Snap sandbox
+    Snap,
+    /// Running as a (possibly unpacked) AppImage
+    AppImage,
+    /// Not running inside any known sandboxed packaging format
+    None,
+    /// Could not determine the packaging format
+    Unknown,
+}
+
+impl std::fmt::Display for Packaging {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Packaging::Flatpak => "Flatpak",
+                Packaging::Snap => "Snap",
+                Packaging::AppImage => "AppImage",
+                Packaging::None => "None",
+                Packaging::Unknown => "Unknown",
+            }
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+
 #[cfg(all(target_os = "windows", not(target_arch = "wasm32")))]
 mod windows;
 #[cfg(all(target_os = "windows", not(target_arch = "wasm32")))]
@@ -270,12 +572,29 @@ pub fn hostname_os() -> OsString {
     hostname().into()
 }
 
+/// Get structured information about the operating system's name, version,
+/// codename and edition.
+#[inline(always)]
+pub fn os_info() -> OsInfo {
+    native::os_info()
+}
+
 /// Get the name of the operating system distribution and (possibly) version.
 ///
 /// Example: "Windows 10" or "Fedora 26 (Workstation Edition)"
+///
+/// This is a convenience wrapper around [`os_info()`](crate::os_info) that
+/// flattens it into a single string; use [`os_info()`](crate::os_info)
+/// directly if you need the distro name, version, or edition separately.
 #[inline(always)]
 pub fn distro() -> String {
-    native::distro().unwrap_or_else(|| format!("Unknown {}", platform()))
+    let info = os_info();
+
+    if info.name.is_none() {
+        return format!("Unknown {}", platform());
+    }
+
+    info.to_string()
 }
 
 /// Get the name of the operating system distribution and (possibly) version.
@@ -283,8 +602,7 @@ pub fn distro() -> String {
 /// Example: "Windows 10" or "Fedora 26 (Workstation Edition)"
 #[inline(always)]
 pub fn distro_os() -> OsString {
-    native::distro_os()
-        .unwrap_or_else(|| format!("Unknown {}", platform()).into())
+    distro().into()
 }
 
 /// Get the desktop environment.
@@ -295,12 +613,46 @@ pub fn desktop_env() -> DesktopEnv {
     native::desktop_env()
 }
 
+/// Get all of the detected desktop environments, in order of preference.
+///
+/// On platforms with a single, well-defined desktop environment this yields
+/// exactly one item (the same as [`desktop_env()`](crate::desktop_env)).  On
+/// Linux, multiple environment signals (`XDG_CURRENT_DESKTOP`,
+/// `XDG_SESSION_DESKTOP`, `DESKTOP_SESSION`, and the window manager) may each
+/// point somewhere slightly different, so every distinct one is returned
+/// (duplicates dropped), most confident first.
+#[inline(always)]
+pub fn desktop_envs() -> impl Iterator<Item = DesktopEnv> {
+    native::desktop_envs()
+}
+
 /// Get the platform.
 #[inline(always)]
 pub fn platform() -> Platform {
     native::platform()
 }
 
+/// Get the CPU architecture of the host.
+#[inline(always)]
+pub fn arch() -> Arch {
+    native::arch()
+}
+
+/// Get the bitness of the *running* operating system, which may differ from
+/// the bitness this crate was compiled for (e.g. a 32-bit process on 64-bit
+/// Windows).
+#[inline(always)]
+pub fn bitness() -> Width {
+    native::bitness()
+}
+
+/// Get the sandboxed/containerized application packaging format (Flatpak,
+/// Snap, AppImage) the process is running under, if any.
+#[inline(always)]
+pub fn packaging() -> Packaging {
+    native::packaging()
+}
+
 /// Get the user's preferred language(s).
 ///
 /// Returned as iterator of two letter language codes (lowercase), optionally
@@ -310,3 +662,48 @@ pub fn platform() -> Platform {
 pub fn lang() -> impl Iterator<Item = String> {
     native::lang()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arch_from_str_matches_common_aliases() {
+        assert_eq!(Arch::from_str("x86_64"), Arch::X64);
+        assert_eq!(Arch::from_str("amd64"), Arch::X64);
+        assert_eq!(Arch::from_str("aarch64"), Arch::Arm64);
+        assert_eq!(Arch::from_str("arm64"), Arch::Arm64);
+        assert_eq!(Arch::from_str("i686"), Arch::X86);
+        assert_eq!(Arch::from_str("armv7l"), Arch::Arm);
+        assert_eq!(Arch::from_str("ppc64le"), Arch::Powerpc64);
+        assert_eq!(Arch::from_str("wasm32"), Arch::Wasm);
+    }
+
+    #[test]
+    fn arch_from_str_falls_back_to_unknown() {
+        assert_eq!(
+            Arch::from_str("nonsense"),
+            Arch::Unknown("nonsense".to_string())
+        );
+    }
+
+    #[test]
+    fn arch_as_str_round_trips_through_from_str() {
+        for arch in [
+            Arch::X86,
+            Arch::X64,
+            Arch::Arm,
+            Arch::Arm64,
+            Arch::Mips,
+            Arch::Mips64,
+            Arch::Powerpc,
+            Arch::Powerpc64,
+            Arch::Riscv64,
+            Arch::S390x,
+            Arch::Sparc64,
+            Arch::Wasm,
+        ] {
+            assert_eq!(Arch::from_str(arch.as_str()), arch);
+        }
+    }
+}
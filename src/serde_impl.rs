@@ -0,0 +1,132 @@
+// Copyright © 2017-2022 The WhoAmI Contributors.
+//
+// Licensed under any of:
+// - Apache License, Version 2.0 (https://www.apache.org/licenses/LICENSE-2.0)
+// - Boost Software License, Version 1.0 (https://www.boost.org/LICENSE_1_0.txt)
+// - MIT License (https://mit-license.org/)
+// At your choosing (See accompanying files LICENSE_APACHE_2_0.txt,
+// LICENSE_MIT.txt and LICENSE_BOOST_1_0.txt).
+
+//! `serde` support for [`Platform`](crate::Platform) and
+//! [`DesktopEnv`](crate::DesktopEnv), gated behind the `serde` feature.
+//!
+//! Both enums round-trip through lowercase/kebab-case strings rather than
+//! serde's default tagged representation, so they embed cleanly in config
+//! files and telemetry payloads.  A handful of common aliases (e.g. `"mac"`
+//! for [`Platform::MacOS`](crate::Platform::MacOS)) are accepted on
+//! deserialize, and the `Unknown(String)` variants serialize to (and parse
+//! from) their contained string directly.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{DesktopEnv, Platform};
+
+impl Serialize for Platform {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match self {
+            Platform::Linux => "linux",
+            Platform::Bsd => "bsd",
+            Platform::Windows => "windows",
+            Platform::MacOS => "macos",
+            Platform::Ios => "ios",
+            Platform::Android => "android",
+            Platform::Nintendo => "nintendo",
+            Platform::Xbox => "xbox",
+            Platform::PlayStation => "playstation",
+            Platform::Fuchsia => "fuchsia",
+            Platform::Redox => "redox",
+            Platform::Unknown(other) => other,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Platform {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+
+        Ok(match text.to_lowercase().as_str() {
+            "linux" => Platform::Linux,
+            "bsd" => Platform::Bsd,
+            "windows" => Platform::Windows,
+            "macos" | "mac" | "mac-os" => Platform::MacOS,
+            "ios" => Platform::Ios,
+            "android" => Platform::Android,
+            "nintendo" => Platform::Nintendo,
+            "xbox" => Platform::Xbox,
+            "playstation" => Platform::PlayStation,
+            "fuchsia" => Platform::Fuchsia,
+            "redox" => Platform::Redox,
+            _ => Platform::Unknown(text),
+        })
+    }
+}
+
+impl Serialize for DesktopEnv {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match self {
+            DesktopEnv::Gnome => "gnome",
+            DesktopEnv::Windows => "windows",
+            DesktopEnv::Lxde => "lxde",
+            DesktopEnv::Openbox => "openbox",
+            DesktopEnv::Mate => "mate",
+            DesktopEnv::Xfce => "xfce",
+            DesktopEnv::Kde => "kde",
+            DesktopEnv::Cinnamon => "cinnamon",
+            DesktopEnv::I3 => "i3",
+            DesktopEnv::Aqua => "aqua",
+            DesktopEnv::Ios => "ios",
+            DesktopEnv::Android => "android",
+            DesktopEnv::WebBrowser => "web-browser",
+            DesktopEnv::Console => "console",
+            DesktopEnv::Ubuntu => "ubuntu",
+            DesktopEnv::Ermine => "ermine",
+            DesktopEnv::Orbital => "orbital",
+            DesktopEnv::Enlightenment => "enlightenment",
+            DesktopEnv::Lxqt => "lxqt",
+            DesktopEnv::Unity => "unity",
+            DesktopEnv::Unknown(other) => other,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for DesktopEnv {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+
+        Ok(match text.to_lowercase().as_str() {
+            "gnome" => DesktopEnv::Gnome,
+            "windows" => DesktopEnv::Windows,
+            "lxde" => DesktopEnv::Lxde,
+            "openbox" => DesktopEnv::Openbox,
+            "mate" => DesktopEnv::Mate,
+            "xfce" => DesktopEnv::Xfce,
+            "kde" | "plasma" => DesktopEnv::Kde,
+            "cinnamon" | "x-cinnamon" => DesktopEnv::Cinnamon,
+            "i3" => DesktopEnv::I3,
+            "aqua" => DesktopEnv::Aqua,
+            "ios" => DesktopEnv::Ios,
+            "android" => DesktopEnv::Android,
+            "web-browser" | "webbrowser" => DesktopEnv::WebBrowser,
+            "console" => DesktopEnv::Console,
+            "ubuntu" => DesktopEnv::Ubuntu,
+            "ermine" => DesktopEnv::Ermine,
+            "orbital" => DesktopEnv::Orbital,
+            "enlightenment" => DesktopEnv::Enlightenment,
+            "lxqt" => DesktopEnv::Lxqt,
+            "unity" => DesktopEnv::Unity,
+            _ => DesktopEnv::Unknown(text),
+        })
+    }
+}
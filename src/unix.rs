@@ -0,0 +1,590 @@
+// Copyright © 2017-2022 The WhoAmI Contributors.
+//
+// Licensed under any of:
+// - Apache License, Version 2.0 (https://www.apache.org/licenses/LICENSE-2.0)
+// - Boost Software License, Version 1.0 (https://www.boost.org/LICENSE_1_0.txt)
+// - MIT License (https://mit-license.org/)
+// At your choosing (See accompanying files LICENSE_APACHE_2_0.txt,
+// LICENSE_MIT.txt and LICENSE_BOOST_1_0.txt).
+
+use std::{
+    env,
+    ffi::{CStr, OsString},
+    fs,
+    mem::MaybeUninit,
+    os::unix::ffi::OsStringExt,
+    path::Path,
+    process::Command,
+};
+
+use crate::{Arch, DesktopEnv, OsInfo, Packaging, Platform, Width};
+
+// Fetch a field out of `/etc/passwd` for the current user via `getpwuid_r()`.
+fn getpwuid(is_real: bool) -> OsString {
+    let mut pwd = MaybeUninit::<libc::passwd>::uninit();
+    let mut result = std::ptr::null_mut();
+    // Default initial buffer size recommended by `getpwuid_r(3)`.
+    let mut buffer = vec![0i8; 16_384];
+
+    let success = loop {
+        let code = unsafe {
+            libc::getpwuid_r(
+                libc::getuid(),
+                pwd.as_mut_ptr(),
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut result,
+            )
+        };
+
+        if code == 0 {
+            break true;
+        } else if code == libc::ERANGE {
+            buffer.resize(buffer.len() * 2, 0);
+        } else {
+            break false;
+        }
+    };
+
+    if !success || result.is_null() {
+        return "Unknown".to_string().into();
+    }
+
+    let pwd = unsafe { pwd.assume_init() };
+    let field = if is_real { pwd.pw_gecos } else { pwd.pw_name };
+
+    if field.is_null() {
+        return "Unknown".to_string().into();
+    }
+
+    let cstr = unsafe { CStr::from_ptr(field) };
+    // The gecos field sometimes contains extra comma-separated fields (room
+    // number, phone, etc).  We only want the real name.
+    let bytes = cstr.to_bytes();
+    let bytes = if is_real {
+        bytes.split(|&b| b == b',').next().unwrap_or(bytes)
+    } else {
+        bytes
+    };
+
+    OsString::from_vec(bytes.to_vec())
+}
+
+pub(crate) fn username_os() -> OsString {
+    getpwuid(false)
+}
+
+pub(crate) fn username() -> String {
+    username_os().to_string_lossy().into_owned()
+}
+
+pub(crate) fn realname_os() -> OsString {
+    let gecos = getpwuid(true);
+
+    if gecos.is_empty() {
+        username_os()
+    } else {
+        gecos
+    }
+}
+
+pub(crate) fn realname() -> String {
+    realname_os().to_string_lossy().into_owned()
+}
+
+pub(crate) fn devicename() -> String {
+    devicename_os().to_string_lossy().into_owned()
+}
+
+pub(crate) fn devicename_os() -> OsString {
+    if let Ok(name) = fs::read_to_string("/etc/machine-info") {
+        for line in name.lines() {
+            if let Some(value) = line.strip_prefix("PRETTY_HOSTNAME=") {
+                let value = value.trim_matches('"');
+
+                if !value.is_empty() {
+                    return value.to_string().into();
+                }
+            }
+        }
+    }
+
+    hostname().into()
+}
+
+pub(crate) fn hostname() -> String {
+    let mut buffer = vec![0u8; 256];
+
+    let code = unsafe {
+        libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len())
+    };
+
+    if code != 0 {
+        return "localhost".to_string();
+    }
+
+    let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    buffer.truncate(end);
+
+    String::from_utf8(buffer).unwrap_or_else(|_| "localhost".to_string())
+}
+
+// Parse `key="value"` or `key=value` lines like those found in `/etc/os-release`.
+fn os_release_line<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.strip_prefix(key)
+        .and_then(|rest| rest.strip_prefix('='))
+        .map(|value| value.trim_matches('"'))
+}
+
+// Split a `VERSION_ID`-style value like "11.2.3" into (major, minor, patch).
+fn split_version(
+    version_id: &str,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let mut parts = version_id.splitn(3, '.');
+
+    (
+        parts.next().filter(|p| !p.is_empty()).map(str::to_string),
+        parts.next().map(str::to_string),
+        parts.next().map(str::to_string),
+    )
+}
+
+// Parse the contents of `/etc/os-release` (or `/usr/lib/os-release`).
+fn parse_os_release(release: &str) -> Option<OsInfo> {
+    let mut info = OsInfo::default();
+    let mut variant = None;
+    let mut variant_id = None;
+
+    for line in release.lines() {
+        if let Some(value) = os_release_line(line, "NAME") {
+            info.name = Some(value.to_string());
+        } else if let Some(value) = os_release_line(line, "VERSION_ID") {
+            let (major, minor, patch) = split_version(value);
+            info.major = major;
+            info.minor = minor;
+            info.patch = patch;
+        } else if let Some(value) = os_release_line(line, "VERSION_CODENAME") {
+            info.codename = Some(value.to_string());
+        } else if let Some(value) = os_release_line(line, "VARIANT_ID") {
+            variant_id = Some(value.to_string());
+        } else if let Some(value) = os_release_line(line, "VARIANT") {
+            variant = Some(value.to_string());
+        }
+    }
+
+    // `VARIANT` is the human-readable form (e.g. "Workstation Edition");
+    // `VARIANT_ID` is a machine-readable slug (e.g. "workstation"). Prefer
+    // the former and only fall back to the latter when it's missing.
+    info.edition = variant.or(variant_id);
+
+    info.name.as_ref()?;
+
+    Some(info)
+}
+
+fn os_info_from_os_release() -> Option<OsInfo> {
+    let release = fs::read_to_string("/etc/os-release")
+        .or_else(|_| fs::read_to_string("/usr/lib/os-release"))
+        .ok()?;
+
+    parse_os_release(&release)
+}
+
+// Parse the output of `lsb_release -a`, which looks like:
+//   Distributor ID: Ubuntu
+//   Release:        22.04
+//   Codename:       jammy
+fn parse_lsb_release(stdout: &str) -> Option<OsInfo> {
+    let mut info = OsInfo::default();
+
+    for line in stdout.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            "Distributor ID" => info.name = Some(value.to_string()),
+            "Release" => {
+                let (major, minor, patch) = split_version(value);
+                info.major = major;
+                info.minor = minor;
+                info.patch = patch;
+            }
+            "Codename" => info.codename = Some(value.to_string()),
+            _ => (),
+        }
+    }
+
+    info.name.as_ref()?;
+
+    Some(info)
+}
+
+fn os_info_from_lsb_release() -> Option<OsInfo> {
+    let output = Command::new("lsb_release").arg("-a").output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_lsb_release(&String::from_utf8_lossy(&output.stdout))
+}
+
+// Parse the output of `sw_vers`, which looks like:
+//   ProductName:    macOS
+//   ProductVersion: 13.4.1
+//   BuildVersion:   22F770820d
+fn parse_sw_vers(stdout: &str) -> Option<OsInfo> {
+    let mut info = OsInfo::default();
+
+    for line in stdout.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            "ProductName" => info.name = Some(value.to_string()),
+            "ProductVersion" => {
+                let (major, minor, patch) = split_version(value);
+                info.major = major;
+                info.minor = minor;
+                info.patch = patch;
+            }
+            _ => (),
+        }
+    }
+
+    info.name.as_ref()?;
+
+    Some(info)
+}
+
+fn os_info_from_sw_vers() -> Option<OsInfo> {
+    let output = Command::new("sw_vers").output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_sw_vers(&String::from_utf8_lossy(&output.stdout))
+}
+
+// Distros without `/etc/os-release` (older Alpine, CentOS) keep a single
+// file containing just the release string, e.g. "3.14.2" or
+// "CentOS release 7.9.2009 (Core)".
+fn os_info_from_release_file(path: &str, name: &str) -> Option<OsInfo> {
+    let release = fs::read_to_string(path).ok()?;
+    let release = release.trim();
+    let version = release
+        .split_whitespace()
+        .find(|word| word.chars().next().is_some_and(|c| c.is_ascii_digit()))
+        .unwrap_or(release);
+    let (major, minor, patch) = split_version(version);
+
+    Some(OsInfo {
+        name: Some(name.to_string()),
+        major,
+        minor,
+        patch,
+        ..OsInfo::default()
+    })
+}
+
+pub(crate) fn os_info() -> OsInfo {
+    if cfg!(target_os = "macos") {
+        if let Some(info) = os_info_from_sw_vers() {
+            return info;
+        }
+    }
+
+    os_info_from_os_release()
+        .or_else(os_info_from_lsb_release)
+        .or_else(|| os_info_from_release_file("/etc/alpine-release", "Alpine"))
+        .or_else(|| os_info_from_release_file("/etc/centos-release", "CentOS"))
+        .unwrap_or_default()
+}
+
+// Map a single lowercased desktop-environment token to a `DesktopEnv`,
+// following the aliases real session managers are known to report.
+fn parse_de_token(token: &str) -> DesktopEnv {
+    match token {
+        "gnome" | "gnome-shell" | "gnome-classic" => DesktopEnv::Gnome,
+        "unity" => DesktopEnv::Unity,
+        "x-cinnamon" | "cinnamon" => DesktopEnv::Cinnamon,
+        "kde" | "kde-plasma" | "plasma" => DesktopEnv::Kde,
+        "mate" => DesktopEnv::Mate,
+        "xfce" | "xfce4" | "xfce5" => DesktopEnv::Xfce,
+        "lxde" => DesktopEnv::Lxde,
+        "lxqt" => DesktopEnv::Lxqt,
+        "openbox" => DesktopEnv::Openbox,
+        "i3" => DesktopEnv::I3,
+        "enlightenment" => DesktopEnv::Enlightenment,
+        "ubuntu" | "ubuntu:gnome" => DesktopEnv::Ubuntu,
+        "" => DesktopEnv::Unknown("Unknown".to_string()),
+        other => DesktopEnv::Unknown(other.to_string()),
+    }
+}
+
+// `gnome-fallback` sessions are Gnome running without compositing; some
+// report `unity` as part of the same token, but they're Gnome underneath.
+fn parse_de(raw: &str) -> DesktopEnv {
+    let token = raw.to_lowercase();
+
+    if token.contains("gnome-fallback") {
+        return DesktopEnv::Gnome;
+    }
+
+    parse_de_token(&token)
+}
+
+// Strip a leading path such as `/usr/share/xsessions/` from `DESKTOP_SESSION`.
+fn strip_session_path(raw: &str) -> &str {
+    raw.rsplit('/').next().unwrap_or(raw)
+}
+
+fn window_manager() -> Option<String> {
+    let output = Command::new("wmctrl").arg("-m").output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Name:"))
+        .map(|name| name.trim().to_lowercase())
+}
+
+/// Return every desktop-environment signal found in the environment, most
+/// preferred first: `XDG_CURRENT_DESKTOP` (which may list several
+/// colon-separated candidates), then `XDG_SESSION_DESKTOP`, then
+/// `DESKTOP_SESSION`, then the running window manager.
+pub(crate) fn desktop_envs() -> impl Iterator<Item = DesktopEnv> {
+    let mut envs = Vec::new();
+
+    if let Ok(current) = env::var("XDG_CURRENT_DESKTOP") {
+        for token in current.split(':') {
+            if !token.is_empty() {
+                envs.push(parse_de(token));
+            }
+        }
+    }
+
+    if let Ok(session) = env::var("XDG_SESSION_DESKTOP") {
+        if !session.is_empty() {
+            envs.push(parse_de(&session));
+        }
+    }
+
+    if let Ok(session) = env::var("DESKTOP_SESSION") {
+        let session = strip_session_path(&session);
+
+        if !session.is_empty() {
+            envs.push(parse_de(session));
+        }
+    }
+
+    if let Some(wm) = window_manager() {
+        envs.push(parse_de(&wm));
+    }
+
+    // The same desktop environment is often reported by more than one
+    // signal (e.g. `XDG_CURRENT_DESKTOP` and the window manager both saying
+    // "gnome"); keep only the first, most-preferred occurrence of each.
+    let mut seen = Vec::new();
+    envs.retain(|env| {
+        if seen.contains(env) {
+            false
+        } else {
+            seen.push(env.clone());
+            true
+        }
+    });
+
+    if envs.is_empty() {
+        envs.push(DesktopEnv::Unknown("Unknown".to_string()));
+    }
+
+    envs.into_iter()
+}
+
+pub(crate) fn desktop_env() -> DesktopEnv {
+    desktop_envs().next().unwrap_or(DesktopEnv::Unknown("Unknown".to_string()))
+}
+
+pub(crate) fn platform() -> Platform {
+    if cfg!(target_os = "macos") {
+        Platform::MacOS
+    } else if cfg!(target_os = "ios") {
+        Platform::Ios
+    } else if cfg!(target_os = "android") {
+        Platform::Android
+    } else if cfg!(target_os = "linux") {
+        Platform::Linux
+    } else if cfg!(any(
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "openbsd",
+        target_os = "netbsd"
+    )) {
+        Platform::Bsd
+    } else if cfg!(target_os = "fuchsia") {
+        Platform::Fuchsia
+    } else if cfg!(target_os = "redox") {
+        Platform::Redox
+    } else {
+        Platform::Unknown(env::consts::OS.to_string())
+    }
+}
+
+pub(crate) fn lang() -> impl Iterator<Item = String> {
+    let langs = env::var("LANGUAGE").unwrap_or_default();
+    let primary = env::var("LANG").ok();
+
+    langs
+        .split(':')
+        .map(str::to_string)
+        .chain(primary)
+        .filter(|lang| !lang.is_empty())
+        .map(|lang| lang.split('.').next().unwrap_or(&lang).replace('_', "-"))
+        .collect::<Vec<String>>()
+        .into_iter()
+}
+
+pub(crate) fn arch() -> Arch {
+    let mut uts = MaybeUninit::<libc::utsname>::uninit();
+
+    let machine = if unsafe { libc::uname(uts.as_mut_ptr()) } == 0 {
+        let uts = unsafe { uts.assume_init() };
+        let cstr = unsafe { CStr::from_ptr(uts.machine.as_ptr()) };
+        cstr.to_string_lossy().into_owned()
+    } else {
+        env::consts::ARCH.to_string()
+    };
+
+    Arch::from_str(&machine)
+}
+
+pub(crate) fn bitness() -> Width {
+    let mut uts = MaybeUninit::<libc::utsname>::uninit();
+
+    if unsafe { libc::uname(uts.as_mut_ptr()) } != 0 {
+        return Width::Unknown;
+    }
+
+    let uts = unsafe { uts.assume_init() };
+    let cstr = unsafe { CStr::from_ptr(uts.machine.as_ptr()) };
+
+    match cstr.to_string_lossy().as_ref() {
+        "x86_64" | "amd64" | "aarch64" | "arm64" | "ppc64" | "ppc64le"
+        | "mips64" | "sparc64" | "s390x" | "riscv64" => Width::X64,
+        "i386" | "i486" | "i586" | "i686" | "armv7" | "armv7l" | "armv6l"
+        | "mips" | "ppc" => Width::X32,
+        _ => Width::Unknown,
+    }
+}
+
+pub(crate) fn packaging() -> Packaging {
+    if Path::new("/.flatpak-info").exists() || env::var_os("FLATPAK_ID").is_some()
+    {
+        return Packaging::Flatpak;
+    }
+
+    if env::var_os("SNAP").is_some() || env::var_os("SNAP_NAME").is_some() {
+        return Packaging::Snap;
+    }
+
+    if env::var_os("APPIMAGE").is_some() || env::var_os("APPDIR").is_some() {
+        return Packaging::AppImage;
+    }
+
+    Packaging::None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_version_handles_one_two_and_three_parts() {
+        assert_eq!(
+            split_version("11"),
+            (Some("11".to_string()), None, None)
+        );
+        assert_eq!(
+            split_version("11.2"),
+            (Some("11".to_string()), Some("2".to_string()), None)
+        );
+        assert_eq!(
+            split_version("11.2.3"),
+            (
+                Some("11".to_string()),
+                Some("2".to_string()),
+                Some("3".to_string())
+            )
+        );
+        assert_eq!(split_version(""), (None, None, None));
+    }
+
+    #[test]
+    fn parse_os_release_prefers_variant_over_variant_id() {
+        // `VARIANT` appears before `VARIANT_ID` here, matching a typical
+        // Fedora `/etc/os-release`; the human-readable form should win.
+        let release = "NAME=\"Fedora\"\n\
+             VERSION_ID=\"26\"\n\
+             VARIANT=\"Workstation Edition\"\n\
+             VARIANT_ID=workstation\n";
+
+        let info = parse_os_release(release).unwrap();
+
+        assert_eq!(info.name.as_deref(), Some("Fedora"));
+        assert_eq!(info.major.as_deref(), Some("26"));
+        assert_eq!(info.edition.as_deref(), Some("Workstation Edition"));
+    }
+
+    #[test]
+    fn parse_os_release_falls_back_to_variant_id() {
+        let release = "NAME=\"Fedora\"\nVARIANT_ID=workstation\n";
+
+        let info = parse_os_release(release).unwrap();
+
+        assert_eq!(info.edition.as_deref(), Some("workstation"));
+    }
+
+    #[test]
+    fn parse_os_release_requires_a_name() {
+        assert!(parse_os_release("VERSION_ID=\"26\"\n").is_none());
+    }
+
+    #[test]
+    fn parse_lsb_release_reads_distributor_release_and_codename() {
+        let stdout = "Distributor ID: Ubuntu\n\
+             Release:        22.04\n\
+             Codename:       jammy\n";
+
+        let info = parse_lsb_release(stdout).unwrap();
+
+        assert_eq!(info.name.as_deref(), Some("Ubuntu"));
+        assert_eq!(info.major.as_deref(), Some("22"));
+        assert_eq!(info.minor.as_deref(), Some("04"));
+        assert_eq!(info.codename.as_deref(), Some("jammy"));
+    }
+
+    #[test]
+    fn parse_sw_vers_reads_product_name_and_version() {
+        let stdout = "ProductName:\tmacOS\nProductVersion:\t13.4.1\n\
+             BuildVersion:\t22F770820d\n";
+
+        let info = parse_sw_vers(stdout).unwrap();
+
+        assert_eq!(info.name.as_deref(), Some("macOS"));
+        assert_eq!(info.major.as_deref(), Some("13"));
+        assert_eq!(info.minor.as_deref(), Some("4"));
+        assert_eq!(info.patch.as_deref(), Some("1"));
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright © 2017-2022 The WhoAmI Contributors.
+//
+// Licensed under any of:
+// - Apache License, Version 2.0 (https://www.apache.org/licenses/LICENSE-2.0)
+// - Boost Software License, Version 1.0 (https://www.boost.org/LICENSE_1_0.txt)
+// - MIT License (https://mit-license.org/)
+// At your choosing (See accompanying files LICENSE_APACHE_2_0.txt,
+// LICENSE_MIT.txt and LICENSE_BOOST_1_0.txt).
+
+use std::ffi::OsString;
+
+use crate::{Arch, DesktopEnv, OsInfo, Packaging, Platform, Width};
+
+// Grab `navigator.userAgent` through web-sys.  Returns `None` outside a
+// browser (e.g. when running under `wasm32-wasi` or a headless runtime with
+// no `window`).
+fn user_agent() -> Option<String> {
+    web_sys::window()?.navigator().user_agent().ok()
+}
+
+// Pull the digits (and separators) following `needle` out of `ua`, e.g.
+// `version_after(ua, "Android ")` or `version_after(ua, "Windows NT ")`.
+fn version_after(ua: &str, needle: &str) -> Option<String> {
+    let start = ua.find(needle)? + needle.len();
+    let rest = &ua[start..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '_')
+        .unwrap_or(rest.len());
+
+    if end == 0 {
+        return None;
+    }
+
+    Some(rest[..end].replace('_', "."))
+}
+
+fn split_version(
+    version: &str,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let mut parts = version.splitn(3, '.');
+
+    (
+        parts.next().filter(|p| !p.is_empty()).map(str::to_string),
+        parts.next().map(str::to_string),
+        parts.next().map(str::to_string),
+    )
+}
+
+// Map a Windows NT kernel version to the consumer-facing release name, the
+// same way installers that sniff `Windows NT X.Y` have to.
+fn windows_name(nt_version: &str) -> &'static str {
+    match nt_version {
+        "10.0" => "10", // Windows 11 also reports "10.0"; UA can't tell them apart
+        "6.3" => "8.1",
+        "6.2" => "8",
+        "6.1" => "7",
+        "6.0" => "Vista",
+        "5.1" | "5.2" => "XP",
+        _ => "Unknown",
+    }
+}
+
+pub(crate) fn platform() -> Platform {
+    let ua = match user_agent() {
+        Some(ua) => ua,
+        None => return Platform::Unknown("Unknown".to_string()),
+    };
+
+    if ua.contains("Windows NT") {
+        Platform::Windows
+    } else if ua.contains("iPhone") || ua.contains("iPad") {
+        Platform::Ios
+    } else if ua.contains("Android") {
+        Platform::Android
+    } else if ua.contains("Mac OS X") {
+        Platform::MacOS
+    } else if ua.contains("Linux") {
+        Platform::Linux
+    } else {
+        Platform::Unknown("Unknown".to_string())
+    }
+}
+
+pub(crate) fn os_info() -> OsInfo {
+    let ua = match user_agent() {
+        Some(ua) => ua,
+        None => return OsInfo::default(),
+    };
+
+    if let Some(version) = version_after(&ua, "Windows NT ") {
+        return OsInfo {
+            name: Some("Windows".to_string()),
+            major: Some(windows_name(&version).to_string()),
+            ..OsInfo::default()
+        };
+    }
+
+    if let Some(version) = version_after(&ua, "Mac OS X ") {
+        let (major, minor, patch) = split_version(&version);
+        return OsInfo {
+            name: Some("Mac OS X".to_string()),
+            major,
+            minor,
+            patch,
+            ..OsInfo::default()
+        };
+    }
+
+    if let Some(version) = version_after(&ua, "Android ") {
+        let (major, minor, patch) = split_version(&version);
+        return OsInfo {
+            name: Some("Android".to_string()),
+            major,
+            minor,
+            patch,
+            ..OsInfo::default()
+        };
+    }
+
+    if ua.contains("iPhone") || ua.contains("iPad") {
+        let version = version_after(&ua, "OS ").unwrap_or_default();
+        let (major, minor, patch) = split_version(&version);
+        return OsInfo {
+            name: Some("iOS".to_string()),
+            major,
+            minor,
+            patch,
+            ..OsInfo::default()
+        };
+    }
+
+    if ua.contains("Linux") {
+        return OsInfo {
+            name: Some("Linux".to_string()),
+            ..OsInfo::default()
+        };
+    }
+
+    OsInfo::default()
+}
+
+pub(crate) fn username_os() -> OsString {
+    "Anonymous".to_string().into()
+}
+
+pub(crate) fn username() -> String {
+    "Anonymous".to_string()
+}
+
+pub(crate) fn realname_os() -> OsString {
+    username_os()
+}
+
+pub(crate) fn realname() -> String {
+    username()
+}
+
+pub(crate) fn devicename_os() -> OsString {
+    devicename().into()
+}
+
+pub(crate) fn devicename() -> String {
+    "Web Browser".to_string()
+}
+
+pub(crate) fn hostname() -> String {
+    "localhost".to_string()
+}
+
+pub(crate) fn desktop_env() -> DesktopEnv {
+    // The underlying OS's desktop environment is irrelevant to a web page;
+    // the browser itself is the "desktop environment" the process sees.
+    DesktopEnv::WebBrowser
+}
+
+pub(crate) fn desktop_envs() -> impl Iterator<Item = DesktopEnv> {
+    std::iter::once(desktop_env())
+}
+
+pub(crate) fn lang() -> impl Iterator<Item = String> {
+    std::iter::once("en-US".to_string())
+}
+
+pub(crate) fn arch() -> Arch {
+    Arch::Wasm
+}
+
+pub(crate) fn bitness() -> Width {
+    Width::X32
+}
+
+pub(crate) fn packaging() -> Packaging {
+    Packaging::None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_after_extracts_trailing_digits() {
+        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64)";
+        assert_eq!(
+            version_after(ua, "Windows NT "),
+            Some("10.0".to_string())
+        );
+    }
+
+    #[test]
+    fn version_after_converts_underscores_to_dots() {
+        let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7)";
+        assert_eq!(
+            version_after(ua, "Mac OS X "),
+            Some("10.15.7".to_string())
+        );
+    }
+
+    #[test]
+    fn version_after_returns_none_when_needle_is_absent() {
+        let ua = "Mozilla/5.0 (X11; Linux x86_64)";
+        assert_eq!(version_after(ua, "Windows NT "), None);
+    }
+
+    #[test]
+    fn version_after_returns_none_when_nothing_follows_the_needle() {
+        let ua = "Mozilla/5.0 (Android )";
+        assert_eq!(version_after(ua, "Android "), None);
+    }
+
+    #[test]
+    fn split_version_handles_one_two_and_three_parts() {
+        assert_eq!(
+            split_version("13"),
+            (Some("13".to_string()), None, None)
+        );
+        assert_eq!(
+            split_version("10.15.7"),
+            (
+                Some("10".to_string()),
+                Some("15".to_string()),
+                Some("7".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn windows_name_maps_known_nt_versions() {
+        assert_eq!(windows_name("10.0"), "10");
+        assert_eq!(windows_name("6.3"), "8.1");
+        assert_eq!(windows_name("6.2"), "8");
+        assert_eq!(windows_name("6.1"), "7");
+        assert_eq!(windows_name("6.0"), "Vista");
+        assert_eq!(windows_name("5.1"), "XP");
+        assert_eq!(windows_name("5.2"), "XP");
+        assert_eq!(windows_name("99.0"), "Unknown");
+    }
+}
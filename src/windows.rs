@@ -0,0 +1,239 @@
+// Copyright © 2017-2022 The WhoAmI Contributors.
+//
+// Licensed under any of:
+// - Apache License, Version 2.0 (https://www.apache.org/licenses/LICENSE-2.0)
+// - Boost Software License, Version 1.0 (https://www.boost.org/LICENSE_1_0.txt)
+// - MIT License (https://mit-license.org/)
+// At your choosing (See accompanying files LICENSE_APACHE_2_0.txt,
+// LICENSE_MIT.txt and LICENSE_BOOST_1_0.txt).
+
+use std::{
+    env, ffi::OsString, mem::MaybeUninit, os::windows::ffi::OsStringExt,
+};
+
+use crate::{Arch, DesktopEnv, OsInfo, Packaging, Platform, Width};
+
+const MAX_NAME: usize = 256;
+
+#[link(name = "advapi32")]
+extern "system" {
+    fn GetUserNameW(buffer: *mut u16, size: *mut u32) -> i32;
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetComputerNameExW(kind: u32, buffer: *mut u16, size: *mut u32) -> i32;
+}
+
+const COMPUTER_NAME_DNS_HOSTNAME: u32 = 1;
+const COMPUTER_NAME_PHYSICAL_DNS_HOSTNAME: u32 = 5;
+
+const PROCESSOR_ARCHITECTURE_AMD64: u16 = 9;
+const PROCESSOR_ARCHITECTURE_ARM64: u16 = 12;
+const PROCESSOR_ARCHITECTURE_IA64: u16 = 6;
+
+// Mirrors the real `SYSTEM_INFO` layout so `GetNativeSystemInfo()` doesn't
+// write past the end of the struct; the fields after
+// `processor_architecture` are part of that layout but unused here.
+#[repr(C)]
+struct SystemInfo {
+    processor_architecture: u16,
+    reserved: u16,
+    page_size: u32,
+    minimum_application_address: usize,
+    maximum_application_address: usize,
+    active_processor_mask: usize,
+    number_of_processors: u32,
+    processor_type: u32,
+    allocation_granularity: u32,
+    processor_level: u16,
+    processor_revision: u16,
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetCurrentProcess() -> isize;
+    fn IsWow64Process(process: isize, result: *mut i32) -> i32;
+    fn GetNativeSystemInfo(info: *mut SystemInfo);
+}
+
+// `GetVersionEx()` is deprecated and lies about the version past Windows 8.1
+// unless the calling binary carries a matching application manifest, so go
+// straight to the kernel via `ntdll`'s `RtlGetVersion()` instead.
+#[repr(C)]
+struct OsVersionInfo {
+    size: u32,
+    major_version: u32,
+    minor_version: u32,
+    build_number: u32,
+    platform_id: u32,
+    csd_version: [u16; 128],
+}
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn RtlGetVersion(info: *mut OsVersionInfo) -> i32;
+}
+
+fn computer_name(kind: u32) -> OsString {
+    let mut buffer = [0u16; MAX_NAME];
+    let mut size = buffer.len() as u32;
+
+    let written = unsafe {
+        GetComputerNameExW(kind, buffer.as_mut_ptr(), &mut size)
+    };
+
+    if written == 0 {
+        return "Unknown".to_string().into();
+    }
+
+    OsString::from_wide(&buffer[..size as usize])
+}
+
+pub(crate) fn username_os() -> OsString {
+    let mut buffer = [0u16; MAX_NAME];
+    let mut size = buffer.len() as u32;
+
+    let written = unsafe { GetUserNameW(buffer.as_mut_ptr(), &mut size) };
+
+    if written == 0 || size == 0 {
+        return "Unknown".to_string().into();
+    }
+
+    // `size` includes the trailing NUL.
+    OsString::from_wide(&buffer[..size as usize - 1])
+}
+
+pub(crate) fn username() -> String {
+    username_os().to_string_lossy().into_owned()
+}
+
+pub(crate) fn realname_os() -> OsString {
+    username_os()
+}
+
+pub(crate) fn realname() -> String {
+    realname_os().to_string_lossy().into_owned()
+}
+
+pub(crate) fn devicename_os() -> OsString {
+    computer_name(COMPUTER_NAME_PHYSICAL_DNS_HOSTNAME)
+}
+
+pub(crate) fn devicename() -> String {
+    devicename_os().to_string_lossy().into_owned()
+}
+
+pub(crate) fn hostname() -> String {
+    computer_name(COMPUTER_NAME_DNS_HOSTNAME)
+        .to_string_lossy()
+        .into_owned()
+}
+
+// Map the kernel version reported by `RtlGetVersion()` to the consumer-facing
+// release name. Windows 11 shares `10.0` with Windows 10, so the build
+// number is what actually tells them apart.
+fn windows_name(major: u32, minor: u32, build: u32) -> &'static str {
+    match (major, minor) {
+        (10, 0) if build >= 22000 => "11",
+        (10, 0) => "10",
+        (6, 3) => "8.1",
+        (6, 2) => "8",
+        (6, 1) => "7",
+        (6, 0) => "Vista",
+        (5, 1) | (5, 2) => "XP",
+        _ => "Unknown",
+    }
+}
+
+pub(crate) fn os_info() -> OsInfo {
+    let mut info = MaybeUninit::<OsVersionInfo>::zeroed();
+    unsafe {
+        (*info.as_mut_ptr()).size =
+            std::mem::size_of::<OsVersionInfo>() as u32;
+    }
+
+    if unsafe { RtlGetVersion(info.as_mut_ptr()) } != 0 {
+        return OsInfo {
+            name: Some("Windows".to_string()),
+            ..OsInfo::default()
+        };
+    }
+
+    let info = unsafe { info.assume_init() };
+    let release = windows_name(
+        info.major_version,
+        info.minor_version,
+        info.build_number,
+    );
+
+    OsInfo {
+        name: Some("Windows".to_string()),
+        major: Some(release.to_string()),
+        ..OsInfo::default()
+    }
+}
+
+pub(crate) fn desktop_env() -> DesktopEnv {
+    DesktopEnv::Windows
+}
+
+pub(crate) fn desktop_envs() -> impl Iterator<Item = DesktopEnv> {
+    std::iter::once(desktop_env())
+}
+
+pub(crate) fn platform() -> Platform {
+    Platform::Windows
+}
+
+pub(crate) fn lang() -> impl Iterator<Item = String> {
+    std::iter::once(
+        env::var("LANG")
+            .unwrap_or_else(|_| "en-US".to_string())
+            .replace('_', "-"),
+    )
+}
+
+pub(crate) fn arch() -> Arch {
+    let arch = env::var("PROCESSOR_ARCHITECTURE").unwrap_or_default();
+
+    match arch.as_str() {
+        "AMD64" => Arch::X64,
+        "x86" => Arch::X86,
+        "ARM64" => Arch::Arm64,
+        "ARM" => Arch::Arm,
+        _ => Arch::from_str(env::consts::ARCH),
+    }
+}
+
+// A 32-bit process on 64-bit Windows runs under WOW64, which
+// `IsWow64Process()` reports directly; otherwise fall back to the native
+// processor architecture reported by `GetNativeSystemInfo()`.
+pub(crate) fn bitness() -> Width {
+    let mut is_wow64 = 0;
+
+    let queried =
+        unsafe { IsWow64Process(GetCurrentProcess(), &mut is_wow64) } != 0;
+
+    if queried && is_wow64 != 0 {
+        return Width::X64;
+    }
+
+    let mut info = std::mem::MaybeUninit::<SystemInfo>::uninit();
+    unsafe { GetNativeSystemInfo(info.as_mut_ptr()) };
+    let info = unsafe { info.assume_init() };
+
+    match info.processor_architecture {
+        PROCESSOR_ARCHITECTURE_AMD64
+        | PROCESSOR_ARCHITECTURE_ARM64
+        | PROCESSOR_ARCHITECTURE_IA64 => Width::X64,
+        0 => Width::X32,
+        _ => Width::Unknown,
+    }
+}
+
+pub(crate) fn packaging() -> Packaging {
+    // MSIX/AppX sandboxing isn't exposed through a simple, stable signal the
+    // way Flatpak/Snap/AppImage are on Linux.
+    Packaging::None
+}